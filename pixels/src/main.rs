@@ -3,11 +3,49 @@ use chip8::*;
 use pixels::{Pixels, SurfaceTexture};
 use winit::{
     application::ApplicationHandler,
-    event::WindowEvent,
+    event::{ElementState, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
+    keyboard::Key,
     window::Window,
 };
 
+/// Maps the standard CHIP-8 keypad layout onto the left half of a QWERTY
+/// keyboard:
+/// ```text
+/// 1 2 3 C        1 2 3 4
+/// 4 5 6 D   ->   Q W E R
+/// 7 8 9 E        A S D F
+/// A 0 B F        Z X C V
+/// ```
+fn key_to_chip8(key: &Key) -> Option<u8> {
+    let Key::Character(s) = key else {
+        return None;
+    };
+
+    // `logical_key` reports the shifted/uppercase character under Shift or
+    // Caps Lock, so normalize before matching or the keypad silently stops
+    // responding in those states.
+    match s.to_lowercase().as_str() {
+        "1" => Some(0x1),
+        "2" => Some(0x2),
+        "3" => Some(0x3),
+        "4" => Some(0xC),
+        "q" => Some(0x4),
+        "w" => Some(0x5),
+        "e" => Some(0x6),
+        "r" => Some(0xD),
+        "a" => Some(0x7),
+        "s" => Some(0x8),
+        "d" => Some(0x9),
+        "f" => Some(0xE),
+        "z" => Some(0xA),
+        "x" => Some(0x0),
+        "c" => Some(0xB),
+        "v" => Some(0xF),
+        _ => None,
+    }
+}
+
 fn main() -> anyhow::Result<()> {
     env_logger::init();
 
@@ -76,37 +114,22 @@ impl ApplicationHandler for App {
             WindowEvent::RedrawRequested => {
                 pixels.render().unwrap();
             }
+            WindowEvent::KeyboardInput { event, .. } => {
+                if let Some(key) = key_to_chip8(&event.logical_key) {
+                    self.program
+                        .set_key(key, event.state == ElementState::Pressed);
+                }
+            }
             _ => (),
         }
 
-        self.program.update(
-            |d| {
-                let mut display = Vec::new();
-
-                for b in d {
-                    display.extend_from_slice(&[
-                        b & 128 != 0,
-                        b & 64 != 0,
-                        b & 32 != 0,
-                        b & 16 != 0,
-                        b & 8 != 0,
-                        b & 4 != 0,
-                        b & 2 != 0,
-                        b & 1 != 0,
-                    ]);
-                }
-
-                for (p, d) in pixels.frame_mut().chunks_exact_mut(4).zip(display) {
-                    p.copy_from_slice(if d {
-                        &[0xFF, 0xFF, 0xFF, 0xFF]
-                    } else {
-                        &[0x00, 0x00, 0x00, 0xFF]
-                    });
-                }
+        let mut redrawn = false;
+        self.program.update(|_| redrawn = true, || {});
 
-                window.request_redraw();
-            },
-            || {},
-        );
+        if redrawn {
+            self.program
+                .render_rgba([0xFF, 0xFF, 0xFF, 0xFF], [0x00, 0x00, 0x00, 0xFF], pixels.frame_mut());
+            window.request_redraw();
+        }
     }
 }
@@ -0,0 +1,197 @@
+//! Interactive, command-driven debugger for [`Chip8`].
+//!
+//! Wraps a [`Chip8`] and adds breakpoints, single-stepping, and a
+//! disassembler, so the `panic!("opcode: ...")` arm in [`Chip8::cycle`]
+//! becomes something a user can break on and inspect instead of a hard
+//! process abort.
+
+use crate::Chip8;
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, vec::Vec};
+
+/// Wraps a [`Chip8`] with breakpoints, stepping, and disassembly.
+pub struct Debugger {
+    chip8: Chip8,
+    breakpoints: Vec<usize>,
+    last_command: Option<String>,
+    repeat_count: u32,
+}
+
+impl Debugger {
+    /// Create a new [`Self`] around an already-initialized [`Chip8`].
+    pub fn new(chip8: Chip8) -> Self {
+        Self {
+            chip8,
+            breakpoints: Vec::new(),
+            last_command: None,
+            repeat_count: 0,
+        }
+    }
+
+    /// Break execution whenever `pc` reaches `addr`.
+    pub fn add_breakpoint(&mut self, addr: usize) {
+        if !self.breakpoints.contains(&addr) {
+            self.breakpoints.push(addr);
+        }
+    }
+
+    /// Stop breaking execution at `addr`.
+    pub fn remove_breakpoint(&mut self, addr: usize) {
+        self.breakpoints.retain(|&bp| bp != addr);
+    }
+
+    pub fn breakpoints(&self) -> &[usize] {
+        &self.breakpoints
+    }
+
+    /// How many times in a row the last command has been repeated via an
+    /// empty [`Self::run_command`] call.
+    pub fn repeat_count(&self) -> u32 {
+        self.repeat_count
+    }
+
+    /// Run exactly one [`Chip8::cycle`], drawing nowhere.
+    pub fn step(&mut self) {
+        self.chip8.cycle(|_| {});
+    }
+
+    /// Step until `pc` lands on a registered breakpoint.
+    /// Returns `false` if there are no breakpoints to stop at.
+    pub fn run_until_breakpoint(&mut self) -> bool {
+        if self.breakpoints.is_empty() {
+            return false;
+        }
+
+        loop {
+            self.step();
+
+            if self.breakpoints.contains(&self.chip8.pc) {
+                return true;
+            }
+        }
+    }
+
+    /// Run a single debugger command. An empty `command` repeats the last
+    /// one (the classic gdb-style "press enter to repeat"), which is why
+    /// `last_command`/`repeat_count` are tracked on [`Self`] rather than
+    /// passed in by the caller each time.
+    ///
+    /// Supported commands: `step [N]`, `run`, `break ADDR`.
+    pub fn run_command(&mut self, command: &str) {
+        let command = if command.trim().is_empty() {
+            self.repeat_count += 1;
+            self.last_command.clone().unwrap_or_default()
+        } else {
+            self.repeat_count = 0;
+            self.last_command = Some(command.into());
+            command.into()
+        };
+
+        let mut parts = command.split_whitespace();
+
+        match parts.next() {
+            Some("step") => {
+                let count = parts.next().and_then(|n| n.parse().ok()).unwrap_or(1);
+
+                for _ in 0..count {
+                    self.step();
+                }
+            }
+            Some("run") => {
+                self.run_until_breakpoint();
+            }
+            Some("break") => {
+                if let Some(addr) = parts.next().and_then(|n| usize::from_str_radix(n.trim_start_matches("0x"), 16).ok()) {
+                    self.add_breakpoint(addr);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Decode the two-byte opcode at `addr` into a mnemonic string,
+    /// e.g. `"JP 0x2A0"` or `"ADD V1, V2"`.
+    pub fn disassemble(&self, addr: usize) -> String {
+        let memory = self.chip8.memory();
+        let hi = memory[addr];
+        let lo = memory[addr + 1];
+        let (o, x, y, n) = (hi >> 4, hi & 0x0F, lo >> 4, lo & 0x0F);
+        let nnn = (x as u16) << 8 | (y as u16) << 4 | n as u16;
+        let nn = lo;
+
+        match o {
+            0x0 if x == 0x0 && y == 0xE && n == 0x0 => "CLS".into(),
+            0x0 if x == 0x0 && y == 0xE && n == 0xE => "RET".into(),
+            0x1 => format!("JP {:#05X}", nnn),
+            0x2 => format!("CALL {:#05X}", nnn),
+            0x3 => format!("SE V{:X}, {:#04X}", x, nn),
+            0x4 => format!("SNE V{:X}, {:#04X}", x, nn),
+            0x5 if n == 0x0 => format!("SE V{:X}, V{:X}", x, y),
+            0x6 => format!("LD V{:X}, {:#04X}", x, nn),
+            0x7 => format!("ADD V{:X}, {:#04X}", x, nn),
+            0x8 if n == 0x0 => format!("LD V{:X}, V{:X}", x, y),
+            0x8 if n == 0x1 => format!("OR V{:X}, V{:X}", x, y),
+            0x8 if n == 0x2 => format!("AND V{:X}, V{:X}", x, y),
+            0x8 if n == 0x3 => format!("XOR V{:X}, V{:X}", x, y),
+            0x8 if n == 0x4 => format!("ADD V{:X}, V{:X}", x, y),
+            0x8 if n == 0x5 => format!("SUB V{:X}, V{:X}", x, y),
+            0x8 if n == 0x6 => format!("SHR V{:X}, V{:X}", x, y),
+            0x8 if n == 0x7 => format!("SUBN V{:X}, V{:X}", x, y),
+            0x8 if n == 0xE => format!("SHL V{:X}, V{:X}", x, y),
+            0x9 if n == 0x0 => format!("SNE V{:X}, V{:X}", x, y),
+            0xA => format!("LD I, {:#05X}", nnn),
+            0xC => format!("RND V{:X}, {:#04X}", x, nn),
+            0xD => format!("DRW V{:X}, V{:X}, {}", x, y, n),
+            0xE if lo == 0x9E => format!("SKP V{:X}", x),
+            0xE if lo == 0xA1 => format!("SKNP V{:X}", x),
+            0xF if lo == 0x07 => format!("LD V{:X}, DT", x),
+            0xF if lo == 0x0A => format!("LD V{:X}, K", x),
+            0xF if lo == 0x15 => format!("LD DT, V{:X}", x),
+            0xF if lo == 0x18 => format!("LD ST, V{:X}", x),
+            0xF if lo == 0x1E => format!("ADD I, V{:X}", x),
+            0xF if lo == 0x29 => format!("LD F, V{:X}", x),
+            0xF if lo == 0x33 => format!("LD B, V{:X}", x),
+            0xF if lo == 0x55 => format!("LD [I], V{:X}", x),
+            0xF if lo == 0x65 => format!("LD V{:X}, [I]", x),
+            _ => format!("DW {:#02X}{:02X}", hi, lo),
+        }
+    }
+
+    pub fn pc(&self) -> usize {
+        self.chip8.pc
+    }
+
+    pub fn i(&self) -> u16 {
+        self.chip8.i
+    }
+
+    pub fn registers(&self) -> [u8; 16] {
+        self.chip8.registers
+    }
+
+    pub fn stack(&self) -> &[u16] {
+        &self.chip8.stack
+    }
+
+    pub fn delay_timer(&self) -> u8 {
+        self.chip8.delay_timer
+    }
+
+    pub fn sound_timer(&self) -> u8 {
+        self.chip8.sound_timer
+    }
+
+    /// Regain ownership of the wrapped [`Chip8`].
+    pub fn into_inner(self) -> Chip8 {
+        self.chip8
+    }
+
+    pub fn chip8(&self) -> &Chip8 {
+        &self.chip8
+    }
+
+    pub fn chip8_mut(&mut self) -> &mut Chip8 {
+        &mut self.chip8
+    }
+}
@@ -9,8 +9,16 @@ extern crate alloc;
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 
+mod debugger;
+pub use debugger::Debugger;
+
+mod snapshot;
+pub use snapshot::Snapshot;
+
+mod time;
+pub use time::{TimeSource, FEMTOS_PER_SEC};
 #[cfg(feature = "std")]
-use std::time::Instant;
+pub use time::StdTimeSource;
 
 const DISPLAY_WIDTH: u8 = 64;
 const DISPLAY_HEIGHT: u8 = 32;
@@ -35,21 +43,77 @@ const FONT_BYTES: [u8; 80] = [
     0xF0, 0x80, 0xF0, 0x80, 0x80, // F
 ];
 
+/// Default seed used to prime [`Chip8::rng_state`] when the caller hasn't
+/// called [`Chip8::seed_rng`]. Must never be `0`, or xorshift64 would be
+/// stuck outputting zeroes forever.
+const DEFAULT_RNG_SEED: u64 = 0xDEAD_BEEF_CAFE_F00D;
+
+/// Default square-wave frequency used by [`Chip8::audio_samples`].
+const DEFAULT_TONE_HZ: f32 = 440.0;
+
+/// Clock rates for [`Chip8::update`]/[`Chip8::update_with`].
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    /// How many [`Chip8::cycle`]s to run per second. Real CHIP-8 hardware
+    /// ran at roughly 500-1000 Hz; 700 is a common middle ground.
+    pub cpu_hz: u32,
+    /// How many times per second to tick `delay_timer`/`sound_timer` down.
+    /// This is fixed at 60 Hz by the original spec; override only for
+    /// deliberately non-standard behavior.
+    pub timer_hz: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            cpu_hz: 700,
+            timer_hz: 60,
+        }
+    }
+}
+
+/// Behavioral toggles for opcodes where real CHIP-8 interpreters disagree.
+/// The defaults match the original COSMAC VIP behavior; set both fields to
+/// `false` to match CHIP-48/SCHIP instead.
+#[derive(Debug, Clone, Copy)]
+pub struct Quirks {
+    /// `8XY6`/`8XYE` shift `Vy` into `Vx` instead of shifting `Vx` in place.
+    pub shift_uses_vy: bool,
+    /// `FX55`/`FX65` leave `i` incremented by `x + 1` afterwards.
+    pub memory_increments_i: bool,
+}
+
+impl Default for Quirks {
+    fn default() -> Self {
+        Self {
+            shift_uses_vy: true,
+            memory_increments_i: true,
+        }
+    }
+}
+
 /// Struct representing the state of a CHIP-8 program
 pub struct Chip8 {
     memory: [u8; 4096],
     display: [u8; DISPLAY_SIZE],
-    pc: usize,
-    i: u16,
-    stack: Vec<u16>,
-    delay_timer: u8,
-    sound_timer: u8,
-    registers: [u8; 16],
+    pub(crate) pc: usize,
+    pub(crate) i: u16,
+    pub(crate) stack: Vec<u16>,
+    pub(crate) delay_timer: u8,
+    pub(crate) sound_timer: u8,
+    pub(crate) registers: [u8; 16],
+    rng_state: u64,
+    keypad: [bool; 16],
+    awaiting_key_release: Option<(u8, u8)>,
+    quirks: Quirks,
+    tone_hz: f32,
+    audio_phase: f32,
+    config: Config,
+    last_cycle_femtos: u128,
+    last_timer_femtos: u128,
 
     #[cfg(feature = "std")]
-    last_cycle: Instant,
-    #[cfg(feature = "std")]
-    last_timer: Instant,
+    time_source: StdTimeSource,
 }
 
 impl Chip8 {
@@ -69,14 +133,64 @@ impl Chip8 {
             delay_timer: 0,
             sound_timer: 0,
             registers: [0; 16],
+            rng_state: DEFAULT_RNG_SEED,
+            keypad: [false; 16],
+            awaiting_key_release: None,
+            quirks: Quirks::default(),
+            tone_hz: DEFAULT_TONE_HZ,
+            audio_phase: 0.0,
+            config: Config::default(),
+            last_cycle_femtos: 0,
+            last_timer_femtos: 0,
 
             #[cfg(feature = "std")]
-            last_cycle: Instant::now(),
-            #[cfg(feature = "std")]
-            last_timer: Instant::now(),
+            time_source: StdTimeSource::new(),
         }
     }
 
+    /// Change the CPU/timer clock rates. Takes effect on the next
+    /// [`Self::update`]/[`Self::update_with`] call.
+    /// `cpu_hz`/`timer_hz` of `0` are replaced with `1` (the slowest
+    /// representable rate instead of a division by zero), the same way
+    /// [`Self::seed_rng`] rejects a `0` seed.
+    pub fn set_config(&mut self, mut config: Config) {
+        config.cpu_hz = config.cpu_hz.max(1);
+        config.timer_hz = config.timer_hz.max(1);
+        self.config = config;
+    }
+
+    /// Reseed the built-in xorshift64 PRNG used by opcode `CXNN`.
+    /// Call this before running a ROM if you need deterministic random
+    /// output (e.g. in tests). `seed` must be non-zero; `0` is replaced
+    /// with [`DEFAULT_RNG_SEED`].
+    pub fn seed_rng(&mut self, seed: u64) {
+        self.rng_state = if seed == 0 { DEFAULT_RNG_SEED } else { seed };
+    }
+
+    /// Advance the xorshift64 PRNG and return its low byte.
+    fn next_random_byte(&mut self) -> u8 {
+        let mut state = self.rng_state;
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        self.rng_state = state;
+
+        (state & 0xFF) as u8
+    }
+
+    /// Report whether the hex key `0x0..=0xF` is currently held down.
+    /// Front-ends should call this on every key-down/key-up event so
+    /// `EX9E`/`EXA1`/`FX0A` see up-to-date state.
+    pub fn set_key(&mut self, key: u8, pressed: bool) {
+        self.keypad[(key & 0xF) as usize] = pressed;
+    }
+
+    /// Change the quirk set used for `8XY6`/`8XYE` and `FX55`/`FX65`.
+    /// See [`Quirks`] for what each flag controls.
+    pub fn set_quirks(&mut self, quirks: Quirks) {
+        self.quirks = quirks;
+    }
+
     /// access memory (useful for debugging).
     pub fn memory(&self) -> [u8; 4096] {
         self.memory
@@ -88,21 +202,55 @@ impl Chip8 {
         self.display
     }
 
+    /// Unpack the 1-bpp display into one `bool` per pixel, row-major,
+    /// starting at the top-left. Prefer [`Self::render_rgba`] if you're
+    /// just going to turn each pixel into a color.
+    pub fn iter_pixels(&self) -> impl Iterator<Item = bool> + '_ {
+        self.display
+            .iter()
+            .flat_map(|byte| (0..8).map(move |bit| byte & (0x80 >> bit) != 0))
+    }
+
+    /// Unpack the display directly into an RGBA buffer, writing `fg` for
+    /// each set pixel and `bg` for each clear one. `out` must be exactly
+    /// `64 * 32 * 4` bytes, e.g. `pixels::Pixels::frame_mut()`.
+    pub fn render_rgba(&self, fg: [u8; 4], bg: [u8; 4], out: &mut [u8]) {
+        for (i, pixel) in self.iter_pixels().enumerate() {
+            out[i * 4..i * 4 + 4].copy_from_slice(if pixel { &fg } else { &bg });
+        }
+    }
+
     /// Run this inside your loop.
     /// Only runs [`Self::cycle`] and [`Self::timers`] when they need to be run.
     #[cfg(feature = "std")]
     pub fn update(&mut self, draw: impl FnMut([u8; DISPLAY_SIZE]), beep: impl FnMut()) {
-        const CLOCK_DUR: f64 = 1. / 700.;
-        const TIMER_DUR: f64 = 1. / 60.;
+        let time_source = self.time_source;
+        self.update_with(&time_source, draw, beep);
+    }
 
-        if self.last_cycle.elapsed().as_secs_f64() > CLOCK_DUR {
+    /// Like [`Self::update`], but paced by a caller-supplied [`TimeSource`]
+    /// instead of [`std::time::Instant`]. This is what makes pacing
+    /// available on `no_std` targets: supply your own monotonic counter
+    /// (a hardware timer, a tick count, whatever the target has) and this
+    /// works the same as `update` does under `std`.
+    pub fn update_with<T: TimeSource>(
+        &mut self,
+        time_source: &T,
+        draw: impl FnMut([u8; DISPLAY_SIZE]),
+        beep: impl FnMut(),
+    ) {
+        let cycle_period = FEMTOS_PER_SEC / self.config.cpu_hz as u128;
+        let timer_period = FEMTOS_PER_SEC / self.config.timer_hz as u128;
+        let now = time_source.elapsed_femtos();
+
+        if now.saturating_sub(self.last_cycle_femtos) >= cycle_period {
             self.cycle(draw);
-            self.last_cycle = Instant::now();
+            self.last_cycle_femtos = now;
         }
 
-        if self.last_timer.elapsed().as_secs_f64() > TIMER_DUR {
+        if now.saturating_sub(self.last_timer_femtos) >= timer_period {
             self.timers(beep);
-            self.last_timer = Instant::now();
+            self.last_timer_femtos = now;
         }
     }
 
@@ -199,6 +347,28 @@ impl Chip8 {
                         self.registers[0xF] = 0;
                     }
                 }
+                0x6 => {
+                    let src = if self.quirks.shift_uses_vy {
+                        self.registers[y as usize]
+                    } else {
+                        self.registers[x as usize]
+                    };
+
+                    let flag = src & 0x1;
+                    self.registers[x as usize] = src >> 1;
+                    self.registers[0xF] = flag;
+                }
+                0xE => {
+                    let src = if self.quirks.shift_uses_vy {
+                        self.registers[y as usize]
+                    } else {
+                        self.registers[x as usize]
+                    };
+
+                    let flag = (src >> 7) & 0x1;
+                    self.registers[x as usize] = src << 1;
+                    self.registers[0xF] = flag;
+                }
                 _ => todo!(),
             },
             0x9 if n == 0 => {
@@ -213,6 +383,11 @@ impl Chip8 {
                 let idx = (x as u16) << 8 | (y as u16) << 4 | n as u16;
                 self.i = idx;
             }
+            0xC => {
+                let nn = y << 4 | n;
+                let value = self.next_random_byte();
+                self.registers[x as usize] = value & nn;
+            }
             0xD => {
                 let x = self.registers[x as usize] & (DISPLAY_WIDTH - 1);
                 let x_byte = x / 8;
@@ -239,6 +414,69 @@ impl Chip8 {
 
                 draw(self.display);
             }
+            0xE => match current2 {
+                0x9E => {
+                    if self.keypad[(self.registers[x as usize] & 0xF) as usize] {
+                        self.pc += 2;
+                    }
+                }
+                0xA1 => {
+                    if !self.keypad[(self.registers[x as usize] & 0xF) as usize] {
+                        self.pc += 2;
+                    }
+                }
+                _ => todo!(),
+            },
+            0xF => match (y, n) {
+                (0x0, 0x7) => self.registers[x as usize] = self.delay_timer,
+                // FX0A: block until a key is pressed, then released, storing
+                // its index in Vx. Re-executes this same instruction (by
+                // rewinding `pc`) until the key is released.
+                (0x0, 0xA) => {
+                    if let Some((_, key)) = self.awaiting_key_release {
+                        if self.keypad[key as usize] {
+                            self.pc -= 2;
+                        } else {
+                            self.awaiting_key_release = None;
+                        }
+                    } else if let Some(key) = (0..16).find(|&k| self.keypad[k]) {
+                        self.registers[x as usize] = key as u8;
+                        self.awaiting_key_release = Some((x, key as u8));
+                        self.pc -= 2;
+                    } else {
+                        self.pc -= 2;
+                    }
+                }
+                (0x1, 0x5) => self.delay_timer = self.registers[x as usize],
+                (0x1, 0x8) => self.sound_timer = self.registers[x as usize],
+                (0x1, 0xE) => self.i = self.i.wrapping_add(self.registers[x as usize] as u16),
+                (0x2, 0x9) => self.i = 0x050 + (self.registers[x as usize] as u16 & 0xF) * 5,
+                (0x3, 0x3) => {
+                    let value = self.registers[x as usize];
+                    self.memory[self.i as usize & 0x0FFF] = value / 100;
+                    self.memory[(self.i as usize + 1) & 0x0FFF] = (value / 10) % 10;
+                    self.memory[(self.i as usize + 2) & 0x0FFF] = value % 10;
+                }
+                (0x5, 0x5) => {
+                    for reg in 0..=x as usize {
+                        self.memory[(self.i as usize + reg) & 0x0FFF] = self.registers[reg];
+                    }
+
+                    if self.quirks.memory_increments_i {
+                        self.i += x as u16 + 1;
+                    }
+                }
+                (0x6, 0x5) => {
+                    for reg in 0..=x as usize {
+                        self.registers[reg] = self.memory[(self.i as usize + reg) & 0x0FFF];
+                    }
+
+                    if self.quirks.memory_increments_i {
+                        self.i += x as u16 + 1;
+                    }
+                }
+                _ => todo!(),
+            },
             _ => panic!("opcode: {:#02x}{:02x}", current, current2),
         }
     }
@@ -253,4 +491,186 @@ impl Chip8 {
             self.sound_timer -= 1;
         }
     }
+
+    /// Freeze the current machine state into a portable [`Snapshot`].
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            memory: self.memory,
+            display: self.display,
+            pc: self.pc,
+            i: self.i,
+            stack: self.stack.clone(),
+            registers: self.registers,
+            delay_timer: self.delay_timer,
+            sound_timer: self.sound_timer,
+            rng_state: self.rng_state,
+        }
+    }
+
+    /// Restore machine state previously captured with [`Self::snapshot`].
+    /// The `std`-only wall-clock time source is reset to "now" rather than
+    /// restored, since a prior run/session's clock reading is meaningless
+    /// here; `update_with` callers on `no_std` targets own their time
+    /// source and should reset it themselves if needed.
+    pub fn restore(&mut self, snapshot: Snapshot) {
+        self.memory = snapshot.memory;
+        self.display = snapshot.display;
+        self.pc = snapshot.pc;
+        self.i = snapshot.i;
+        self.stack = snapshot.stack;
+        self.registers = snapshot.registers;
+        self.delay_timer = snapshot.delay_timer;
+        self.sound_timer = snapshot.sound_timer;
+        self.rng_state = snapshot.rng_state;
+        self.last_cycle_femtos = 0;
+        self.last_timer_femtos = 0;
+
+        #[cfg(feature = "std")]
+        {
+            self.time_source = StdTimeSource::new();
+        }
+    }
+
+    /// Set the frequency of the square wave played while `sound_timer > 0`.
+    /// Defaults to [`DEFAULT_TONE_HZ`] (~440 Hz, concert A).
+    pub fn set_tone_hz(&mut self, hz: f32) {
+        self.tone_hz = hz;
+    }
+
+    /// Fill `out` with PCM samples for the built-in beep: a square wave at
+    /// [`Self::set_tone_hz`] while `sound_timer > 0`, silence otherwise.
+    /// The phase accumulator lives on `self`, so consecutive calls produce
+    /// a continuous, click-free waveform rather than restarting each time.
+    pub fn audio_samples(&mut self, sample_rate: u32, out: &mut [i16]) {
+        const AMPLITUDE: i16 = i16::MAX / 4;
+
+        let phase_step = self.tone_hz / sample_rate as f32;
+
+        for sample in out.iter_mut() {
+            if self.sound_timer == 0 {
+                *sample = 0;
+                continue;
+            }
+
+            *sample = if self.audio_phase < 0.5 { AMPLITUDE } else { -AMPLITUDE };
+
+            self.audio_phase += phase_step;
+            while self.audio_phase >= 1.0 {
+                self.audio_phase -= 1.0;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cxnn_masks_the_generated_byte() {
+        let mut reference = Chip8::new(&[], None);
+        reference.seed_rng(0x1234_5678);
+        let expected = reference.next_random_byte() & 0x0F;
+
+        let mut chip8 = Chip8::new(&[0xC0, 0x0F], None);
+        chip8.seed_rng(0x1234_5678);
+        chip8.cycle(|_| {});
+
+        assert_eq!(chip8.registers[0], expected);
+    }
+
+    #[test]
+    fn fx0a_blocks_until_key_is_pressed_then_released() {
+        let mut chip8 = Chip8::new(&[0xF0, 0x0A], None);
+
+        // No key down yet: re-executes the same instruction forever.
+        chip8.cycle(|_| {});
+        assert_eq!(chip8.pc, 0x200);
+
+        chip8.set_key(5, true);
+        chip8.cycle(|_| {});
+        assert_eq!(chip8.registers[0], 5);
+        assert_eq!(chip8.pc, 0x200, "still blocked until the key is released");
+
+        // Still held: keeps blocking.
+        chip8.cycle(|_| {});
+        assert_eq!(chip8.pc, 0x200);
+
+        chip8.set_key(5, false);
+        chip8.cycle(|_| {});
+        assert_eq!(chip8.pc, 0x202, "advances once the key is released");
+    }
+
+    #[test]
+    fn shr_into_vf_preserves_the_shift_out_flag() {
+        // 8XY6 with x == 0xF: the destination register is VF itself, so
+        // the flag write must win over the shifted result.
+        let mut chip8 = Chip8::new(&[0x8F, 0x06], None);
+        chip8.registers[0] = 0b10; // src is Vy (quirks default shift_uses_vy)
+
+        chip8.cycle(|_| {});
+
+        assert_eq!(chip8.registers[0xF], 0, "shifted-out bit, not the shift result");
+    }
+
+    #[test]
+    fn shl_into_vf_preserves_the_shift_out_flag() {
+        // 8XY E with x == 0xF: same hazard as SHR, shifting left instead.
+        let mut chip8 = Chip8::new(&[0x8F, 0x0E], None);
+        chip8.registers[0] = 0b1000_0001; // src is Vy (quirks default shift_uses_vy)
+
+        chip8.cycle(|_| {});
+
+        assert_eq!(chip8.registers[0xF], 1, "shifted-out bit, not the shift result");
+    }
+
+    #[test]
+    fn restore_reproduces_a_prior_snapshot() {
+        let mut chip8 = Chip8::new(&[0x60, 0x2A, 0xA1, 0x23], None);
+        chip8.seed_rng(42);
+        chip8.cycle(|_| {});
+        chip8.cycle(|_| {});
+
+        let snapshot = chip8.snapshot();
+
+        // Mutate further so restoring is actually exercised.
+        chip8.registers[0] = 0xFF;
+        chip8.i = 0;
+
+        chip8.restore(snapshot.clone());
+
+        assert_eq!(chip8.snapshot(), snapshot);
+    }
+
+    #[test]
+    fn fx55_wraps_instead_of_indexing_past_memory() {
+        // ANNN 0x0FFF, then FX55 with x == 1: writes V0 at the last valid
+        // memory address and V1 one byte past it, which must wrap around
+        // to address 0 rather than panic.
+        let mut chip8 = Chip8::new(&[0xAF, 0xFF, 0xF1, 0x55], None);
+        chip8.registers[0] = 0xAA;
+        chip8.registers[1] = 0xBB;
+
+        chip8.cycle(|_| {});
+        chip8.cycle(|_| {});
+
+        assert_eq!(chip8.memory()[0x0FFF], 0xAA);
+        assert_eq!(chip8.memory()[0x0000], 0xBB);
+    }
+
+    #[test]
+    fn set_config_rejects_a_zero_hz_rate() {
+        let mut chip8 = Chip8::new(&[], None);
+        chip8.set_config(Config { cpu_hz: 0, timer_hz: 0 });
+
+        struct ZeroTime;
+        impl TimeSource for ZeroTime {
+            fn elapsed_femtos(&self) -> u128 {
+                0
+            }
+        }
+
+        // Would divide by zero before cpu_hz/timer_hz were clamped.
+        chip8.update_with(&ZeroTime, |_| {}, || {});
+    }
 }
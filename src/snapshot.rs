@@ -0,0 +1,124 @@
+//! Save-state support: freeze a running [`Chip8`] into a [`Snapshot`] and
+//! restore it later, e.g. for instant save/load in a front-end or
+//! deterministic replay tests when paired with [`Chip8::seed_rng`].
+
+use crate::DISPLAY_SIZE;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// A portable copy of [`Chip8`]'s machine state: memory, display, `pc`,
+/// `i`, stack, registers, both timers and the RNG state. The time-keeping
+/// state used by `Chip8::update`/`Chip8::update_with` is not captured; it
+/// is reset on [`Chip8::restore`]. Keypad state, a pending `FX0A` key wait,
+/// quirks, clock config and tone settings are not captured either, and
+/// `restore` leaves them as they are.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snapshot {
+    pub memory: [u8; 4096],
+    pub display: [u8; DISPLAY_SIZE],
+    pub pc: usize,
+    pub i: u16,
+    pub stack: Vec<u16>,
+    pub registers: [u8; 16],
+    pub delay_timer: u8,
+    pub sound_timer: u8,
+    pub rng_state: u64,
+}
+
+impl Snapshot {
+    /// Pack this snapshot into a flat byte blob. Available even under
+    /// `no_std`/without the `serde` feature, for targets that can't pull in
+    /// a JSON/bincode dependency.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(4096 + DISPLAY_SIZE + 4 + 2 + 2 + self.stack.len() * 2 + 16 + 1 + 1 + 8);
+
+        out.extend_from_slice(&self.memory);
+        out.extend_from_slice(&self.display);
+        out.extend_from_slice(&(self.pc as u32).to_le_bytes());
+        out.extend_from_slice(&self.i.to_le_bytes());
+        out.extend_from_slice(&(self.stack.len() as u16).to_le_bytes());
+        for frame in &self.stack {
+            out.extend_from_slice(&frame.to_le_bytes());
+        }
+        out.extend_from_slice(&self.registers);
+        out.push(self.delay_timer);
+        out.push(self.sound_timer);
+        out.extend_from_slice(&self.rng_state.to_le_bytes());
+
+        out
+    }
+
+    /// Unpack a byte blob produced by [`Self::to_bytes`].
+    ///
+    /// # Panics
+    /// Panics if `bytes` is truncated or otherwise malformed.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let mut cursor = 0;
+        let mut take = |len: usize| {
+            let slice = &bytes[cursor..cursor + len];
+            cursor += len;
+            slice
+        };
+
+        let mut memory = [0; 4096];
+        memory.copy_from_slice(take(4096));
+
+        let mut display = [0; DISPLAY_SIZE];
+        display.copy_from_slice(take(DISPLAY_SIZE));
+
+        let pc = u32::from_le_bytes(take(4).try_into().unwrap()) as usize;
+        let i = u16::from_le_bytes(take(2).try_into().unwrap());
+
+        let stack_len = u16::from_le_bytes(take(2).try_into().unwrap()) as usize;
+        let mut stack = Vec::with_capacity(stack_len);
+        for _ in 0..stack_len {
+            stack.push(u16::from_le_bytes(take(2).try_into().unwrap()));
+        }
+
+        let mut registers = [0; 16];
+        registers.copy_from_slice(take(16));
+
+        let delay_timer = take(1)[0];
+        let sound_timer = take(1)[0];
+        let rng_state = u64::from_le_bytes(take(8).try_into().unwrap());
+
+        Self {
+            memory,
+            display,
+            pc,
+            i,
+            stack,
+            registers,
+            delay_timer,
+            sound_timer,
+            rng_state,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_bytes_from_bytes_round_trips() {
+        let snapshot = Snapshot {
+            memory: [0x42; 4096],
+            display: [0xAA; DISPLAY_SIZE],
+            pc: 0x2F0,
+            i: 0x0ABC,
+            stack: Vec::from([0x200, 0x2F0, 0x300]),
+            registers: [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16],
+            delay_timer: 30,
+            sound_timer: 15,
+            rng_state: 0xDEAD_BEEF_CAFE_F00D,
+        };
+
+        let bytes = snapshot.to_bytes();
+        let restored = Snapshot::from_bytes(&bytes);
+
+        assert_eq!(restored, snapshot);
+    }
+}
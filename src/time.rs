@@ -0,0 +1,45 @@
+//! Time source abstraction so [`Chip8::update_with`] can pace execution on
+//! targets that have no `std::time::Instant`, by supplying their own
+//! monotonic counter instead.
+
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+/// A monotonic clock, measured in femtoseconds (1e-15s) since some
+/// arbitrary, fixed reference point. Femtoseconds (rather than, say,
+/// nanoseconds) keep the rounding of integer clock periods like `1 / 700`
+/// of a second below one femtosecond, instead of comparing `f64` seconds.
+pub trait TimeSource {
+    fn elapsed_femtos(&self) -> u128;
+}
+
+/// Femtoseconds per second, for converting to/from other time units.
+pub const FEMTOS_PER_SEC: u128 = 1_000_000_000_000_000;
+
+/// [`TimeSource`] backed by [`std::time::Instant`], for hosted targets.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy)]
+pub struct StdTimeSource {
+    start: Instant,
+}
+
+#[cfg(feature = "std")]
+impl StdTimeSource {
+    pub fn new() -> Self {
+        Self { start: Instant::now() }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for StdTimeSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl TimeSource for StdTimeSource {
+    fn elapsed_femtos(&self) -> u128 {
+        self.start.elapsed().as_nanos() * (FEMTOS_PER_SEC / 1_000_000_000)
+    }
+}